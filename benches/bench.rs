@@ -1,9 +1,8 @@
-use std::fs;
 use std::hint::black_box;
-use std::path::PathBuf;
 
 use criterion::{Criterion, Throughput, criterion_group, criterion_main};
 use rustc_lexer::{Cursor, FrontmatterAllowed};
+use rustc_lexer_benchmark::corpus;
 
 fn bench_cursor_first(c: &mut Criterion) {
     let input = "fn main() { println!(\"Hello, world!\"); }";
@@ -362,76 +361,31 @@ fn main() {}
 }
 
 fn bench_tokenize_real_world(c: &mut Criterion) {
-    let home = std::env::var("HOME").expect("HOME not set");
-    let toolchain_src = PathBuf::from(home)
-        .join(".rustup")
-        .join("toolchains")
-        .join(if cfg!(target_os = "windows") {
-            format!("stable-{}-pc-windows-msvc", std::env::consts::ARCH)
-        } else if cfg!(target_os = "macos") {
-            format!("stable-{}-apple-darwin", std::env::consts::ARCH)
-        } else {
-            format!("stable-{}-unknown-linux-gnu", std::env::consts::ARCH)
-        })
-        .join("lib")
-        .join("rustlib")
-        .join("src")
-        .join("rust")
-        .join("library");
-
-    let mut sources: Vec<(String, String)> = Vec::new();
-    let mut total_bytes = 0usize;
-
-    fn collect_rs_files(
-        dir: &PathBuf,
-        sources: &mut Vec<(String, String)>,
-        total_bytes: &mut usize,
-    ) {
-        if let Ok(entries) = fs::read_dir(dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_dir() {
-                    collect_rs_files(&path, sources, total_bytes);
-                } else if path.extension().map_or(false, |ext| ext == "rs") {
-                    if let Ok(content) = fs::read_to_string(&path) {
-                        *total_bytes += content.len();
-                        sources.push((path.display().to_string(), content));
-                    }
-                }
-            }
-        }
-    }
-
-    collect_rs_files(&toolchain_src, &mut sources, &mut total_bytes);
-
-    if sources.is_empty() {
-        eprintln!("Warning: No .rs files found in {:?}", toolchain_src);
-        return;
-    }
-
-    sources.sort_by(|a, b| a.0.cmp(&b.0));
+    let corpus = corpus::load();
 
     println!(
         "Found {} files, {} total",
-        sources.len(),
-        if total_bytes >= 1_000_000 {
-            format!("{:.2} MB", total_bytes as f64 / 1_000_000.0)
-        } else if total_bytes >= 1_000 {
-            format!("{:.2} KB", total_bytes as f64 / 1_000.0)
+        corpus.files.len(),
+        if corpus.total_bytes >= 1_000_000 {
+            format!("{:.2} MB", corpus.total_bytes as f64 / 1_000_000.0)
+        } else if corpus.total_bytes >= 1_000 {
+            format!("{:.2} KB", corpus.total_bytes as f64 / 1_000.0)
         } else {
-            format!("{} bytes", total_bytes)
+            format!("{} bytes", corpus.total_bytes)
         }
     );
 
     let mut group = c.benchmark_group("tokenize_real_world");
-    group.throughput(Throughput::Bytes(total_bytes as u64));
+    group.throughput(Throughput::Bytes(corpus.total_bytes as u64));
     group.sample_size(100);
 
     group.bench_function("stdlib_all_files", |b| {
         b.iter(|| {
             let mut token_count = 0usize;
-            for (_path, content) in &sources {
-                for token in rustc_lexer::tokenize(black_box(content), FrontmatterAllowed::No) {
+            for file in &corpus.files {
+                for token in
+                    rustc_lexer::tokenize(black_box(&file.content), FrontmatterAllowed::No)
+                {
                     black_box(token);
                     token_count += 1;
                 }
@@ -443,9 +397,43 @@ fn bench_tokenize_real_world(c: &mut Criterion) {
     group.finish();
 }
 
+// Tokenizes the whole corpus concatenated into one string in a single
+// call, isolating steady-state throughput from the per-file setup cost
+// that dominates bench_tokenize_real_world.
+fn bench_tokenize_real_world_concatenated(c: &mut Criterion) {
+    let corpus = corpus::load();
+
+    let mut concatenated = String::with_capacity(corpus.total_bytes + corpus.files.len());
+    for file in &corpus.files {
+        // Without a separator, a file that doesn't end on a token
+        // boundary would have its last token merge with the next file's
+        // first token, silently changing the token count.
+        concatenated.push_str(&file.content);
+        concatenated.push('\n');
+    }
+
+    let mut group = c.benchmark_group("tokenize_real_world_concatenated");
+    group.throughput(Throughput::Bytes(concatenated.len() as u64));
+    group.sample_size(100);
+
+    group.bench_function("stdlib_single_string", |b| {
+        b.iter(|| {
+            let mut token_count = 0usize;
+            for token in rustc_lexer::tokenize(black_box(&concatenated), FrontmatterAllowed::No) {
+                black_box(token);
+                token_count += 1;
+            }
+            black_box(token_count)
+        })
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_tokenize_real_world,
+    bench_tokenize_real_world_concatenated,
     bench_strip_shebang,
     bench_tokenize,
     bench_frontmatter,
@@ -0,0 +1,129 @@
+// Re-lexes every file in the corpus with rustc_lexer and, independently,
+// with proc-macro2, and fails if the two disagree on token boundaries.
+
+use std::ops::Range;
+use std::process::ExitCode;
+
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+use rustc_lexer::{FrontmatterAllowed, TokenKind};
+use rustc_lexer_benchmark::corpus;
+
+// Flattens a token stream into the byte range of each leaf token, in
+// source order, including group delimiters. Also undoes two proc-macro2
+// desugarings that have no counterpart in rustc_lexer: a lifetime like
+// `'a` comes back as a separate Punct + Ident pair (merged here into one
+// span), and a doc comment comes back as a synthetic `#[doc = "..."]` /
+// `#![doc = "..."]` sequence whose tokens all carry the comment's own
+// span (dropped here, since rustc_lexer just emits the comment as one
+// skipped token).
+fn flatten_spans(tokens: TokenStream, spans: &mut Vec<Range<usize>>) {
+    let trees: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut i = 0;
+    while i < trees.len() {
+        match &trees[i] {
+            TokenTree::Group(group) => {
+                spans.push(group.span_open().byte_range());
+                flatten_spans(group.stream(), spans);
+                spans.push(group.span_close().byte_range());
+                i += 1;
+            }
+            TokenTree::Punct(punct) if punct.as_char() == '\'' && punct.spacing() == Spacing::Joint => {
+                if let Some(TokenTree::Ident(ident)) = trees.get(i + 1) {
+                    spans.push(punct.span().byte_range().start..ident.span().byte_range().end);
+                    i += 2;
+                } else {
+                    spans.push(punct.span().byte_range());
+                    i += 1;
+                }
+            }
+            TokenTree::Punct(punct) if punct.as_char() == '#' => {
+                let has_bang = matches!(trees.get(i + 1), Some(TokenTree::Punct(p)) if p.as_char() == '!');
+                let group_index = i + 1 + has_bang as usize;
+                let is_doc_sugar = matches!(
+                    trees.get(group_index),
+                    Some(TokenTree::Group(group))
+                        if group.delimiter() == Delimiter::Bracket
+                            && group.span().byte_range() == punct.span().byte_range()
+                );
+                if is_doc_sugar {
+                    i = group_index + 1;
+                } else {
+                    spans.push(punct.span().byte_range());
+                    i += 1;
+                }
+            }
+            TokenTree::Ident(ident) => {
+                spans.push(ident.span().byte_range());
+                i += 1;
+            }
+            TokenTree::Punct(punct) => {
+                spans.push(punct.span().byte_range());
+                i += 1;
+            }
+            TokenTree::Literal(literal) => {
+                spans.push(literal.span().byte_range());
+                i += 1;
+            }
+        }
+    }
+}
+
+// Token boundaries as produced by rustc_lexer, skipping whitespace and
+// comments: proc-macro2 discards whitespace and plain comments, and the
+// doc-comment sequences it does produce are dropped by flatten_spans.
+fn rustc_lexer_spans(content: &str) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut pos = 0usize;
+    for token in rustc_lexer::tokenize(content, FrontmatterAllowed::No) {
+        let start = pos;
+        let end = pos + token.len as usize;
+        pos = end;
+        match token.kind {
+            TokenKind::Whitespace | TokenKind::LineComment { .. } | TokenKind::BlockComment { .. } => {
+                continue;
+            }
+            _ => spans.push(start..end),
+        }
+    }
+    spans
+}
+
+fn verify_file(path: &str, content: &str) -> Result<(), String> {
+    let reference: TokenStream = content
+        .parse()
+        .map_err(|err| format!("proc-macro2 failed to parse: {err}"))?;
+    let mut reference_spans = Vec::new();
+    flatten_spans(reference, &mut reference_spans);
+
+    let actual_spans = rustc_lexer_spans(content);
+
+    if actual_spans != reference_spans {
+        return Err(format!(
+            "token boundaries diverge in {path}: rustc_lexer produced {} token(s), proc-macro2 {}",
+            actual_spans.len(),
+            reference_spans.len(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let corpus = corpus::load();
+    let mut failures = 0usize;
+
+    for file in &corpus.files {
+        if let Err(message) = verify_file(&file.path, &file.content) {
+            eprintln!("MISMATCH: {message}");
+            failures += 1;
+        }
+    }
+
+    println!("verified {} files, {} mismatch(es)", corpus.files.len(), failures);
+
+    if failures == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,146 @@
+// Downloads a pinned rust-lang/rust revision into target/bench-corpus/
+// and walks library/ + compiler/ for .rs files, for use as a
+// reproducible real-world benchmark corpus.
+
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use tar::Archive;
+
+// rust-lang/rust revision the corpus is pinned to (tag 1.79.0). Bump
+// deliberately; never track a moving branch.
+const REVISION: &str = "129f3b9964af4d4a709d1383930ade12dfe7c081";
+
+// Paths (relative to the extracted rust-{REVISION}/ root) known to
+// contain intentionally malformed UTF-8 or otherwise non-lexable
+// fixtures.
+const EXCLUDE: &[&str] = &[
+    "library/alloc/src/tests/fuzz_chunk_bad_utf8.rs",
+    "library/core/tests/str_fuzz_bad_utf8.rs",
+];
+
+const INCLUDE_DIRS: &[&str] = &["library", "compiler"];
+
+pub struct CorpusFile {
+    pub path: String,
+    pub content: String,
+}
+
+pub struct Corpus {
+    pub files: Vec<CorpusFile>,
+    pub total_bytes: usize,
+}
+
+fn cache_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("bench-corpus")
+        .join(REVISION)
+}
+
+// Downloads and extracts the tarball for REVISION into
+// target/bench-corpus/<rev>/, skipping the download if it's already
+// extracted.
+fn ensure_extracted(dest: &Path) -> io::Result<PathBuf> {
+    let extracted_root = dest.join(format!("rust-{REVISION}"));
+    if extracted_root.join("library").is_dir() {
+        return Ok(extracted_root);
+    }
+
+    fs::create_dir_all(dest)?;
+
+    let url = format!("https://github.com/rust-lang/rust/archive/{REVISION}.tar.gz");
+    let response = ureq::get(&url).call().map_err(io::Error::other)?;
+
+    let mut tarball = Vec::new();
+    response
+        .into_reader()
+        .read_to_end(&mut tarball)
+        .map_err(io::Error::other)?;
+
+    Archive::new(GzDecoder::new(&tarball[..])).unpack(dest)?;
+
+    Ok(extracted_root)
+}
+
+fn excluded_index(relative_path: &Path) -> Option<usize> {
+    EXCLUDE
+        .iter()
+        .position(|excluded| relative_path == Path::new(excluded))
+}
+
+// A file outside EXCLUDE that still fails to read as UTF-8 means the
+// list is stale, which would otherwise corrupt the file/byte counts
+// silently. Panic instead of skipping it so the list gets fixed.
+fn collect_rs_files(
+    dir: &Path,
+    root: &Path,
+    files: &mut Vec<CorpusFile>,
+    total_bytes: &mut usize,
+    matched_excludes: &mut [bool],
+) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_rs_files(&path, root, files, total_bytes, matched_excludes);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            let relative_path = path.strip_prefix(root).unwrap_or(&path);
+            let exclude_index = excluded_index(relative_path);
+            if let Some(index) = exclude_index {
+                matched_excludes[index] = true;
+            }
+            match fs::read_to_string(&path) {
+                Ok(content) => {
+                    if exclude_index.is_some() {
+                        continue;
+                    }
+                    *total_bytes += content.len();
+                    files.push(CorpusFile {
+                        path: relative_path.display().to_string(),
+                        content,
+                    });
+                }
+                Err(_) if exclude_index.is_some() => continue,
+                Err(err) => panic!(
+                    "{} failed to read as UTF-8 and is not in EXCLUDE: {err}",
+                    relative_path.display(),
+                ),
+            }
+        }
+    }
+}
+
+// Panics if the tarball can't be fetched or extracted; a benchmark with
+// no corpus to measure isn't a useful result to report.
+pub fn load() -> Corpus {
+    let dest = cache_root();
+    let extracted_root =
+        ensure_extracted(&dest).unwrap_or_else(|err| panic!("failed to prepare corpus: {err}"));
+
+    let mut files = Vec::new();
+    let mut total_bytes = 0usize;
+    let mut matched_excludes = vec![false; EXCLUDE.len()];
+    for dir in INCLUDE_DIRS {
+        collect_rs_files(
+            &extracted_root.join(dir),
+            &extracted_root,
+            &mut files,
+            &mut total_bytes,
+            &mut matched_excludes,
+        );
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    for (path, matched) in EXCLUDE.iter().zip(&matched_excludes) {
+        if !matched {
+            eprintln!("warning: EXCLUDE entry {path:?} did not match any file in the corpus");
+        }
+    }
+
+    Corpus { files, total_bytes }
+}
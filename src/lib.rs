@@ -0,0 +1,2 @@
+// Shared by the benches and the `verify` binary.
+pub mod corpus;